@@ -12,11 +12,79 @@
 
 use std::cmp;
 use std::ffi::OsString;
+use std::fmt;
 use std::fs;
 use std::fs::File;
 use std::fs::OpenOptions as OpenOptionsFs;
 use std::io::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A fully custom volume-naming function: given the first volume's path and
+/// a volume index (starting at 1), returns that volume's path.
+pub type NamingFn = Arc<dyn Fn(&Path, usize) -> PathBuf + Send + Sync>;
+
+/// The strategy used to derive the path of each volume after the first from
+/// the path of the first volume and the volume's index.
+///
+/// The first volume is always the path passed to `open`/`create` unchanged;
+/// a `NamingScheme` only controls how volumes after it are named.
+#[derive(Clone, Default)]
+pub enum NamingScheme {
+    /// Appends `.{index}`, e.g. `path`, `path.2`, `path.3`, ... (the
+    /// default). Lexical sort order breaks down once the index reaches
+    /// double digits (`path.10` sorts before `path.2`).
+    #[default]
+    DottedIndex,
+
+    /// Appends `.{index}` zero-padded to `width` digits, e.g. `archive`,
+    /// `archive.002`, `archive.003`, ... (7-Zip style), which keeps parts in
+    /// lexical sort order.
+    ZeroPadded {
+        /// Number of digits the index is padded to.
+        width: usize,
+    },
+
+    /// Appends `.part{index}` zero-padded to `width` digits, e.g. `name`,
+    /// `name.part02`, `name.part03`, ...
+    PartNumbered {
+        /// Number of digits the part number is padded to.
+        width: usize,
+    },
+
+    /// A fully custom naming function. See [`NamingFn`].
+    Custom(NamingFn),
+}
+
+impl fmt::Debug for NamingScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamingScheme::DottedIndex => f.write_str("DottedIndex"),
+            NamingScheme::ZeroPadded { width } => f.debug_struct("ZeroPadded").field("width", width).finish(),
+            NamingScheme::PartNumbered { width } => f.debug_struct("PartNumbered").field("width", width).finish(),
+            NamingScheme::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl NamingScheme {
+    fn by_index(&self, path: &Path, index: usize) -> PathBuf {
+        if index == 1 {
+            return path.to_path_buf();
+        }
+
+        let suffix = match self {
+            NamingScheme::DottedIndex => format!(".{}", index),
+            NamingScheme::ZeroPadded { width } => format!(".{:0width$}", index, width = width),
+            NamingScheme::PartNumbered { width } => format!(".part{:0width$}", index, width = width),
+            NamingScheme::Custom(f) => return f(path, index),
+        };
+
+        let mut os = path.to_path_buf().into_os_string();
+        os.push(OsString::from(suffix));
+        PathBuf::from(os)
+    }
+}
 
 /// Options and flags which can be used to configure how a file is opened.
 ///
@@ -30,32 +98,153 @@ pub struct OpenOptions {
     truncate: bool,
     create: bool,
     create_new: bool,
+    #[cfg(unix)]
+    mode: Option<u32>,
+    #[cfg(unix)]
+    custom_flags: Option<i32>,
+    naming: NamingScheme,
+    buffer_size: usize,
 }
 
-/// A reference to an open set of volumes on the filesystem.
+/// A storage backend capable of providing the individual volumes that make
+/// up a `SplitFile`.
+///
+/// `VolumeStore` is the extension point that lets `SplitFile` allocate its
+/// volumes somewhere other than the local filesystem, much like the
+/// storage-driver abstraction an embedded filesystem sits on top of an
+/// arbitrary block device. A volume is identified by a `Path`, but that path
+/// is only ever used as an opaque key for implementations that aren't
+/// backed by real files (e.g. an in-memory store).
+///
+/// [`FsStore`] is the default implementation, backing each volume with a
+/// `std::fs::File`.
+pub trait VolumeStore {
+    /// The handle returned for an open volume. It must support reading,
+    /// writing and seeking, as `SplitFile` treats it exactly like a file.
+    type Handle: Read + Write + Seek;
+
+    /// Opens (or creates, depending on `opts`) the volume identified by
+    /// `path`.
+    ///
+    /// `first_open` tracks whether this is the first volume opened for the
+    /// containing `SplitFile`, mirroring the special handling `fs::File`
+    /// needs for `append`/`truncate`/`create_new` on the first volume versus
+    /// subsequent ones.
+    fn open(&mut self, path: &Path, opts: &OpenOptions, first_open: &mut bool) -> Result<Self::Handle>;
+
+    /// Returns `true` if a volume already exists at `path`.
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Removes the volume at `path`.
+    ///
+    /// Implementations should return an error of kind `ErrorKind::NotFound`
+    /// if no volume exists at `path`, mirroring `fs::remove_file`.
+    fn remove(&mut self, path: &Path) -> Result<()>;
+
+    /// Resizes an open volume's handle to exactly `size` bytes, as
+    /// `std::fs::File::set_len` does.
+    fn set_len(&mut self, handle: &mut Self::Handle, size: u64) -> Result<()>;
+}
+
+/// The default [`VolumeStore`], backing every volume with a `std::fs::File`
+/// on the local filesystem.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FsStore;
+
+impl VolumeStore for FsStore {
+    type Handle = File;
+
+    fn open(&mut self, path: &Path, opts: &OpenOptions, first_open: &mut bool) -> Result<File> {
+        let w = match (*first_open, opts.append) {
+            (false, true) => true,
+            _ => opts.write,
+        };
+        let c = match (*first_open, opts.append, opts.create_new, opts.write) {
+            (false, true, _, _) | (false, _, true, _) | (false, _, _, true) => true,
+            _ => opts.create,
+        };
+        let cn = match (*first_open, opts.create_new) {
+            (false, true) => false,
+            _ => opts.create_new,
+        };
+        let t = match (*first_open, opts.truncate) {
+            (false, true) => false,
+            _ => opts.truncate,
+        };
+
+        if *first_open {
+            *first_open = false;
+        }
+
+        let mut o = OpenOptionsFs::new();
+        o.read(opts.read)
+            .write(w)
+            .append(false)
+            .truncate(t)
+            .create(c)
+            .create_new(cn);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            if let Some(mode) = opts.mode {
+                o.mode(mode);
+            }
+            if let Some(flags) = opts.custom_flags {
+                o.custom_flags(flags);
+            }
+        }
+
+        o.open(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn remove(&mut self, path: &Path) -> Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn set_len(&mut self, handle: &mut File, size: u64) -> Result<()> {
+        handle.set_len(size)
+    }
+}
+
+/// A reference to an open set of volumes.
 ///
 /// An instance of SplitFile can be read and/or written in the same way as a
-/// single file is via `fs::File`, but with data allocated
-/// across volumes.
+/// single file is via `fs::File`, but with data allocated across volumes.
 ///
 /// Second and subsequent volumes written use the path and filename of the
 /// first volume, and append the extension ".n", where n is the index of each
 /// respective volume.
 ///
+/// SplitFile is generic over the [`VolumeStore`] that provides its volumes,
+/// defaulting to [`FsStore`] so volumes live on the local filesystem exactly
+/// as before.
+///
 /// SplitFile implements Read, Write and Seek traits.
 #[derive(Debug)]
-pub struct SplitFile {
-    volumes: Vec<Volume>,
+pub struct SplitFile<S: VolumeStore = FsStore> {
+    store: S,
+    volumes: Vec<Volume<S::Handle>>,
     path: PathBuf,
     opts: OpenOptions,
     volsize: u64,
     index: usize,
     first_open: bool,
+    buffer_size: usize,
+    read_buf: Vec<u8>,
+    read_pos: usize,
+    read_len: usize,
+    write_buf: Vec<u8>,
+    write_len: usize,
 }
 
 #[derive(Debug)]
-struct Volume {
-    file: File,
+struct Volume<H> {
+    file: H,
     pos: u64,
     reset: bool,
 }
@@ -64,6 +253,7 @@ struct Volume {
 struct Filenames {
     path: PathBuf,
     index: usize,
+    naming: NamingScheme,
 }
 
 impl Iterator for Filenames {
@@ -71,25 +261,16 @@ impl Iterator for Filenames {
 
     fn next(&mut self) -> Option<Self::Item> {
         self.index += 1;
-        Some(Filenames::by_index(self.path.clone(), self.index))
+        Some(self.naming.by_index(&self.path, self.index))
     }
 }
 
 impl Filenames {
-    fn new(path: PathBuf, start_index: usize) -> Filenames {
+    fn new(path: PathBuf, start_index: usize, naming: NamingScheme) -> Filenames {
         Filenames {
             path,
             index: start_index - 1,
-        }
-    }
-
-    fn by_index(path: PathBuf, index: usize) -> PathBuf {
-        if index == 1 {
-            path
-        } else {
-            let mut os = path.into_os_string();
-            os.push(OsString::from(format!(".{}", index.to_string())));
-            PathBuf::from(os)
+            naming,
         }
     }
 }
@@ -161,91 +342,45 @@ impl OpenOptions {
         self
     }
 
-    /// Opens a file at `path` with the options specified by `self`.  Path refers
-    /// to the path of the first volume.  Volsize is the maximum size of each
-    /// volume.
-    pub fn open<P: AsRef<Path>>(&self, path: P, volsize: u64) -> Result<SplitFile> {
-        self._open(path.as_ref(), volsize)
-    }
-
-    fn _open(&self, path: &Path, volsize: u64) -> Result<SplitFile> {
-        SplitFile::new(path, self, volsize)
-    }
-}
-
-impl Volume {
-    fn open(path: PathBuf, opts: &OpenOptions, first_open: &mut bool) -> Result<Volume> {
-        Ok(Volume {
-            file: Volume::open_file(path, opts, first_open)?,
-            pos: 0,
-            reset: false,
-        })
-    }
-
-    fn open_file(path: PathBuf, opts: &OpenOptions, first_open: &mut bool) -> Result<File> {
-        let w = match (*first_open, opts.append) {
-            (false, true) => true,
-            _ => opts.write,
-        };
-        let c = match (*first_open, opts.append, opts.create_new, opts.write) {
-            (false, true, _, _) | (false, _, true, _) | (false, _, _, true) => true,
-            _ => opts.create,
-        };
-        let cn = match (*first_open, opts.create_new) {
-            (false, true) => false,
-            _ => opts.create_new,
-        };
-        let t = match (*first_open, opts.truncate) {
-            (false, true) => false,
-            _ => opts.truncate,
-        };
-
-        if *first_open {
-            *first_open = false;
-        }
-
-        OpenOptionsFs::new()
-            .read(opts.read)
-            .write(w)
-            .append(false)
-            .truncate(t)
-            .create(c)
-            .create_new(cn)
-            .open(path)
+    /// Sets the naming scheme used to derive the path of volumes after the
+    /// first. Defaults to [`NamingScheme::DottedIndex`].
+    pub fn naming_scheme(&mut self, naming: NamingScheme) -> &mut OpenOptions {
+        self.naming = naming;
+        self
     }
 
-    fn init_volumes(path: &Path, opts: &OpenOptions, first_open: &mut bool) -> Result<Vec<Volume>> {
-        Ok(Filenames::new(path.to_path_buf(), 1)
-            .enumerate()
-            .take_while(|(i, p)| *i == 0 || p.is_file())
-            .map(|(_, p): (_, PathBuf)| -> Result<Volume> {
-                Ok(Volume::open(p, opts, first_open)?)
-            })
-            .collect::<Result<Vec<Volume>>>()?)
+    /// Enables an internal read/write buffer of `buffer_size` bytes.
+    ///
+    /// When set, reads are satisfied from an aligned read buffer refilled in
+    /// `buffer_size` chunks and writes accumulate in a write buffer until it
+    /// fills, cutting the number of underlying syscalls for byte-at-a-time
+    /// consumers. Disabled (unbuffered) by default.
+    pub fn buffer_size(&mut self, buffer_size: usize) -> &mut OpenOptions {
+        self.buffer_size = buffer_size;
+        self
     }
 
-    fn truncate_volumes(path: &Path) -> Result<()> {
-        for p in Filenames::new(path.to_path_buf(), 2) {
-            if let Err(e) = fs::remove_file(p) {
-                match e.kind() {
-                    ErrorKind::NotFound => break,
-                    _ => return Err(e),
-                }
-            }
-        }
-        Ok(())
+    /// Opens a file at `path` with the options specified by `self`.  Path refers
+    /// to the path of the first volume.  Volsize is the maximum size of each
+    /// volume.
+    ///
+    /// Volumes are provided by [`FsStore`], i.e. the local filesystem. Use
+    /// [`open_in`] to supply a custom [`VolumeStore`].
+    ///
+    /// [`open_in`]: #method.open_in
+    pub fn open<P: AsRef<Path>>(&self, path: P, volsize: u64) -> Result<SplitFile> {
+        self.open_in(FsStore, path, volsize)
     }
 
-    fn chk_reset(&mut self) -> Result<()> {
-        if self.reset {
-            self.pos = self.file.seek(SeekFrom::Start(0))?;
-            self.reset = false;
-        }
-        Ok(())
+    /// Opens a file at `path` with the options specified by `self`, using
+    /// `store` to provide the underlying volumes instead of the local
+    /// filesystem.
+    pub fn open_in<S: VolumeStore, P: AsRef<Path>>(&self, store: S, path: P, volsize: u64) -> Result<SplitFile<S>> {
+        SplitFile::with_store(store, path.as_ref(), self, volsize)
     }
 }
 
-impl Read for Volume {
+impl<H: Read> Read for Volume<H> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         let r = self.file.read(buf)?;
         self.pos += r as u64;
@@ -253,7 +388,7 @@ impl Read for Volume {
     }
 }
 
-impl Write for Volume {
+impl<H: Write> Write for Volume<H> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
         let r = self.file.write(buf)?;
         self.pos += r as u64;
@@ -265,12 +400,22 @@ impl Write for Volume {
     }
 }
 
-impl SplitFile {
+impl<H: Seek> Volume<H> {
+    fn chk_reset(&mut self) -> Result<()> {
+        if self.reset {
+            self.pos = self.file.seek(SeekFrom::Start(0))?;
+            self.reset = false;
+        }
+        Ok(())
+    }
+}
+
+impl SplitFile<FsStore> {
     /// Attempts to open a file in read-only mode.
     ///
     /// See the `OpenOptions::open` method for more details.
     pub fn open<P: AsRef<Path>>(path: P, volsize: u64) -> Result<SplitFile> {
-        OpenOptions::new().read(true)._open(path.as_ref(), volsize)
+        OpenOptions::new().read(true).open_in(FsStore, path.as_ref(), volsize)
     }
 
     /// Opens a file in write-only mode.
@@ -282,24 +427,110 @@ impl SplitFile {
             .write(true)
             .create(true)
             .truncate(true)
-            ._open(path.as_ref(), volsize)
+            .open_in(FsStore, path.as_ref(), volsize)
+    }
+
+    /// Returns aggregated metadata across every volume currently backing
+    /// this file.
+    ///
+    /// Modeled on `std::fs::File::metadata`, this stats the last volume for
+    /// its true size (as `len` does) plus `volsize * (n - 1)` for the rest,
+    /// and queries `fs::metadata` for each volume's path.
+    pub fn metadata(&mut self) -> Result<SplitMetadata> {
+        let n = self.volumes.len();
+        let last_len = {
+            let v = self.volumes.last_mut().expect("No volumes exist");
+            v.pos = v.file.seek(SeekFrom::End(0))?;
+            v.reset = true;
+            v.pos
+        };
+
+        let mut volume_sizes = Vec::with_capacity(n);
+        let mut volume_paths = Vec::with_capacity(n);
+        for i in 0..n {
+            let p = self.opts.naming.by_index(&self.path, i + 1);
+            let size = if i + 1 == n { last_len } else { fs::metadata(&p)?.len() };
+            volume_sizes.push(size);
+            volume_paths.push(p);
+        }
+
+        Ok(SplitMetadata {
+            len: (cmp::max(n - 1, 0) as u64 * self.volsize) + last_len,
+            volumes: n,
+            volume_sizes,
+            volume_paths,
+        })
+    }
+}
+
+/// Aggregated metadata about a `SplitFile`, as returned by
+/// [`SplitFile::metadata`].
+#[derive(Clone, Debug)]
+pub struct SplitMetadata {
+    len: u64,
+    volumes: usize,
+    volume_sizes: Vec<u64>,
+    volume_paths: Vec<PathBuf>,
+}
+
+impl SplitMetadata {
+    /// Returns the total logical length of the file, i.e. the sum of every
+    /// volume's size.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of volumes currently backing the file.
+    pub fn volumes(&self) -> usize {
+        self.volumes
+    }
+
+    /// Returns the byte size of each volume, in order.
+    pub fn volume_sizes(&self) -> &[u64] {
+        &self.volume_sizes
     }
 
-    fn new(path: &Path, opts: &OpenOptions, volsize: u64) -> Result<SplitFile> {
+    /// Returns the path of each volume, in order.
+    pub fn volume_paths(&self) -> &[PathBuf] {
+        &self.volume_paths
+    }
+}
+
+impl<S: VolumeStore> SplitFile<S> {
+    /// Opens a file at `path`, using `store` to provide its volumes.
+    ///
+    /// See [`OpenOptions::open_in`] for more details.
+    pub fn with_store(store: S, path: &Path, opts: &OpenOptions, volsize: u64) -> Result<SplitFile<S>> {
+        SplitFile::new(store, path, opts, volsize)
+    }
+
+    fn new(mut store: S, path: &Path, opts: &OpenOptions, volsize: u64) -> Result<SplitFile<S>> {
         if opts.truncate {
-            Volume::truncate_volumes(path)?;
+            SplitFile::<S>::truncate_volumes(&mut store, path, &opts.naming)?;
         }
 
         let mut first_open = true;
-        let vols = Volume::init_volumes(path, opts, &mut first_open)?;
+        let vols = SplitFile::<S>::init_volumes(&mut store, path, opts, &mut first_open)?;
 
         let mut sf = SplitFile {
+            store,
             volumes: vols,
             opts: opts.clone(),
             path: path.to_path_buf(),
             volsize: volsize,
             index: 1,
             first_open: first_open,
+            buffer_size: opts.buffer_size,
+            read_buf: Vec::new(),
+            read_pos: 0,
+            read_len: 0,
+            write_buf: vec![0; opts.buffer_size],
+            write_len: 0,
         };
 
         if opts.append {
@@ -308,27 +539,185 @@ impl SplitFile {
         Ok(sf)
     }
 
-    fn add_volume(&mut self) -> Result<&mut Volume> {
+    fn init_volumes(
+        store: &mut S,
+        path: &Path,
+        opts: &OpenOptions,
+        first_open: &mut bool,
+    ) -> Result<Vec<Volume<S::Handle>>> {
+        let mut vols = Vec::new();
+        for (i, p) in Filenames::new(path.to_path_buf(), 1, opts.naming.clone()).enumerate() {
+            if i != 0 && !store.exists(&p) {
+                break;
+            }
+            vols.push(Volume {
+                file: store.open(&p, opts, first_open)?,
+                pos: 0,
+                reset: false,
+            });
+        }
+        Ok(vols)
+    }
+
+    fn truncate_volumes(store: &mut S, path: &Path, naming: &NamingScheme) -> Result<()> {
+        SplitFile::<S>::remove_volumes_from(store, path, 2, naming)
+    }
+
+    /// Removes every volume of `path` from `start_index` onward, stopping as
+    /// soon as one is missing.
+    fn remove_volumes_from(store: &mut S, path: &Path, start_index: usize, naming: &NamingScheme) -> Result<()> {
+        for p in Filenames::new(path.to_path_buf(), start_index, naming.clone()) {
+            if let Err(e) = store.remove(&p) {
+                match e.kind() {
+                    ErrorKind::NotFound => break,
+                    _ => return Err(e),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of volumes and the byte size of the last one
+    /// needed to hold a logical file of `size` bytes given `volsize`.
+    fn vol_count_and_rem(volsize: u64, size: u64) -> (usize, u64) {
+        if size == 0 {
+            (1, 0)
+        } else if size.is_multiple_of(volsize) {
+            ((size / volsize) as usize, volsize)
+        } else {
+            ((size / volsize + 1) as usize, size % volsize)
+        }
+    }
+
+    fn add_volume(&mut self) -> Result<&mut Volume<S::Handle>> {
         let index = self.volumes.len() + 1;
-        self.volumes.push(Volume::open(
-            Filenames::by_index(self.path.clone(), index),
-            &self.opts,
-            &mut self.first_open,
-        )?);
+        self.volumes.push(Volume {
+            file: self
+                .store
+                .open(&self.opts.naming.by_index(&self.path, index), &self.opts, &mut self.first_open)?,
+            pos: 0,
+            reset: false,
+        });
         Ok(self.volumes.last_mut().unwrap())
     }
 
     fn len(&mut self) -> Result<u64> {
-        let v: &mut Volume = self.volumes.last_mut().expect("No volumes exist");
+        let v = self.volumes.last_mut().expect("No volumes exist");
         v.pos = v.file.seek(SeekFrom::End(0))?;
         v.reset = true;
         let last_file_size = v.pos;
         Ok((cmp::max(self.volumes.len() - 1, 0) as u64 * self.volsize) + last_file_size)
     }
-}
 
-impl Read for SplitFile {
-    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+    /// Returns the logical cursor position, flushing any buffered writes and
+    /// invalidating (without physically rewinding) any buffered read-ahead
+    /// first, so the returned position and the underlying volume cursors
+    /// agree afterward.
+    fn current_pos(&mut self) -> Result<u64> {
+        self.flush_write_buf()?;
+        let unread = (self.read_len - self.read_pos) as u64;
+        self.read_len = 0;
+        self.read_pos = 0;
+        Ok(((self.index - 1) as u64 * self.volsize) + self.volumes[self.index - 1].pos - unread)
+    }
+
+    /// Resizes the logical file to `size` bytes, treating the concatenation
+    /// of all volumes as a single stream.
+    ///
+    /// Shrinking truncates the volume that now holds the new end and removes
+    /// every volume beyond it. Growing fills the current last volume up to
+    /// `volsize` and appends new volumes (relying on sparse zero-fill) until
+    /// the final one holds the remainder. The cursor is clamped to the new
+    /// end if it now sits past it.
+    pub fn set_len(&mut self, size: u64) -> Result<()> {
+        let volsize = self.volsize;
+        let cur_pos = self.current_pos()?;
+        let cur_len = self.len()?;
+        let (keep, rem) = SplitFile::<S>::vol_count_and_rem(volsize, size);
+
+        if size < cur_len {
+            self.store.set_len(&mut self.volumes[keep - 1].file, rem)?;
+            self.volumes[keep - 1].reset = true;
+            self.volumes.truncate(keep);
+            SplitFile::<S>::remove_volumes_from(&mut self.store, &self.path, keep + 1, &self.opts.naming)?;
+        } else if size > cur_len {
+            if keep > self.volumes.len() {
+                let last_idx = self.volumes.len() - 1;
+                self.store.set_len(&mut self.volumes[last_idx].file, volsize)?;
+                self.volumes[last_idx].reset = true;
+
+                while self.volumes.len() < keep - 1 {
+                    self.add_volume()?;
+                    let idx = self.volumes.len() - 1;
+                    self.store.set_len(&mut self.volumes[idx].file, volsize)?;
+                    self.volumes[idx].reset = true;
+                }
+                self.add_volume()?;
+            }
+            let last = self.volumes.last_mut().unwrap();
+            self.store.set_len(&mut last.file, rem)?;
+            last.reset = true;
+        }
+
+        self.seek(SeekFrom::Start(cmp::min(cur_pos, size)))?;
+        Ok(())
+    }
+
+    /// Refills the read buffer from the current cursor, in a chunk no
+    /// larger than the current volume's remaining bytes so a single refill
+    /// never straddles a volume boundary.
+    fn fill_read_buf(&mut self) -> Result<()> {
+        let eff_pos = |v: &Volume<S::Handle>| if v.reset { 0 } else { v.pos };
+
+        if eff_pos(&self.volumes[self.index - 1]) >= self.volsize && self.index < self.volumes.len() {
+            self.index += 1;
+        }
+        let vpos = eff_pos(&self.volumes[self.index - 1]);
+        let want = cmp::min(self.buffer_size as u64, self.volsize - vpos) as usize;
+
+        let mut buf = std::mem::take(&mut self.read_buf);
+        if buf.len() < want {
+            buf.resize(want, 0);
+        }
+        let n = self.read_raw(&mut buf[..want])?;
+        self.read_buf = buf;
+        self.read_pos = 0;
+        self.read_len = n;
+        Ok(())
+    }
+
+    /// Discards any buffered, unread read-ahead, rewinding the underlying
+    /// volume cursor back over the unread bytes first so the logical and
+    /// physical positions agree again (the read-ahead never straddles a
+    /// volume boundary, so the rewind always targets `self.index`).
+    fn invalidate_read_buf(&mut self) -> Result<()> {
+        let unread = (self.read_len - self.read_pos) as u64;
+        self.read_pos = 0;
+        self.read_len = 0;
+        if unread > 0 {
+            let v = &mut self.volumes[self.index - 1];
+            v.pos = v.file.seek(SeekFrom::Current(-(unread as i64)))?;
+        }
+        Ok(())
+    }
+
+    /// Writes out any bytes accumulated in the write buffer, looping until
+    /// all of them land (mirroring `write_all`).
+    fn flush_write_buf(&mut self) -> Result<()> {
+        if self.write_len == 0 {
+            return Ok(());
+        }
+        let buf = std::mem::take(&mut self.write_buf);
+        let mut n = 0;
+        while n < self.write_len {
+            n += self.write_raw(&buf[n..self.write_len])?;
+        }
+        self.write_buf = buf;
+        self.write_len = 0;
+        Ok(())
+    }
+
+    fn read_raw(&mut self, buf: &mut [u8]) -> Result<usize> {
         let blen = buf.len();
         let mut rt: usize = 0;
 
@@ -345,8 +734,33 @@ impl Read for SplitFile {
     }
 }
 
-impl Write for SplitFile {
-    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+impl<S: VolumeStore> Read for SplitFile<S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.buffer_size == 0 {
+            return self.read_raw(buf);
+        }
+
+        self.flush_write_buf()?;
+
+        let mut rt = 0;
+        while rt < buf.len() {
+            if self.read_pos >= self.read_len {
+                self.fill_read_buf()?;
+                if self.read_len == 0 {
+                    break;
+                }
+            }
+            let n = cmp::min(buf.len() - rt, self.read_len - self.read_pos);
+            buf[rt..rt + n].copy_from_slice(&self.read_buf[self.read_pos..self.read_pos + n]);
+            self.read_pos += n;
+            rt += n;
+        }
+        Ok(rt)
+    }
+}
+
+impl<S: VolumeStore> SplitFile<S> {
+    fn write_raw(&mut self, buf: &[u8]) -> Result<usize> {
         let blen = buf.len();
         let volsize = self.volsize;
         let mut wt: usize = 0;
@@ -364,7 +778,7 @@ impl Write for SplitFile {
         if wt < blen {
             for i in self.index.. {
                 self.index = i + 1;
-                let v: &mut Volume = self.add_volume()?;
+                let v = self.add_volume()?;
                 let wlen = cmp::min(blen - wt, (volsize - v.pos) as usize);
                 wt += v.write(&buf[wt..wt + wlen])?;
                 if wt == blen {
@@ -376,16 +790,46 @@ impl Write for SplitFile {
         Ok(wt)
     }
 
-    fn flush(&mut self) -> Result<()> {
+    fn flush_raw(&mut self) -> Result<()> {
         for v in self.volumes.iter_mut() {
             v.flush()?;
         }
         Ok(())
     }
-}
 
-impl Seek for SplitFile {
-    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+    /// Buffers `buf` for later writing, flushing the write buffer whenever
+    /// it fills or the current volume runs out of room, so the buffer never
+    /// spans a volume boundary.
+    fn write_buffered(&mut self, buf: &[u8]) -> Result<usize> {
+        self.invalidate_read_buf()?;
+
+        let mut n = 0;
+        while n < buf.len() {
+            let vpos = self.volumes[self.index - 1].pos + self.write_len as u64;
+            let room_in_vol = self.volsize - vpos;
+
+            if room_in_vol == 0 {
+                self.flush_write_buf()?;
+                n += self.write_raw(&buf[n..n + 1])?;
+                continue;
+            }
+
+            let space_in_buf = self.buffer_size - self.write_len;
+            if space_in_buf == 0 {
+                self.flush_write_buf()?;
+                continue;
+            }
+
+            let take = cmp::min(cmp::min(room_in_vol as usize, space_in_buf), buf.len() - n);
+            self.write_buf[self.write_len..self.write_len + take].copy_from_slice(&buf[n..n + take]);
+            self.write_len += take;
+            n += take;
+        }
+
+        Ok(n)
+    }
+
+    fn seek_raw(&mut self, pos: SeekFrom) -> Result<u64> {
         let mut filesize: u64 = 0;
 
         if let SeekFrom::End(_) = pos {
@@ -411,7 +855,15 @@ impl Seek for SplitFile {
         }
 
         self.index = ((apos / self.volsize) + 1) as usize;
-        let vpos = apos - ((self.index - 1) as u64 * self.volsize);
+        let mut vpos = apos - ((self.index - 1) as u64 * self.volsize);
+
+        //an apos that lands exactly on the end of a volume-aligned file
+        //resolves to one past the last existing volume; pull it back to
+        //the end of the last volume instead of indexing past it
+        if self.index > self.volumes.len() {
+            self.index = self.volumes.len();
+            vpos = self.volsize;
+        }
 
         self.volumes[self.index - 1].pos = self.volumes[self.index - 1]
             .file
@@ -426,6 +878,44 @@ impl Seek for SplitFile {
     }
 }
 
+impl<S: VolumeStore> Write for SplitFile<S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.buffer_size == 0 {
+            return self.write_raw(buf);
+        }
+        self.write_buffered(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flush_write_buf()?;
+        self.flush_raw()
+    }
+}
+
+impl<S: VolumeStore> Seek for SplitFile<S> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        if self.buffer_size == 0 {
+            return self.seek_raw(pos);
+        }
+
+        let pos = match pos {
+            SeekFrom::Current(off) => SeekFrom::Start(safe_add(self.current_pos()?, off)?),
+            other => {
+                self.flush_write_buf()?;
+                self.invalidate_read_buf()?;
+                other
+            }
+        };
+        self.seek_raw(pos)
+    }
+}
+
+impl<S: VolumeStore> Drop for SplitFile<S> {
+    fn drop(&mut self) {
+        let _ = self.flush_write_buf();
+    }
+}
+
 fn safe_add(nu64: u64, ni64: i64) -> Result<u64> {
     if ni64 >= 0 {
         Ok(nu64 + (ni64 as u64))
@@ -442,6 +932,38 @@ fn safe_add(nu64: u64, ni64: i64) -> Result<u64> {
     }
 }
 
+/// Unix-specific extensions.
+#[cfg(unix)]
+pub mod unix {
+    use super::OpenOptions;
+
+    /// Unix-specific extensions to [`OpenOptions`], mirroring
+    /// `std::os::unix::fs::OpenOptionsExt`.
+    pub trait OpenOptionsExt {
+        /// Sets the mode bits that every created volume will have, as
+        /// `std::os::unix::fs::OpenOptionsExt::mode` does for `fs::File`.
+        fn mode(&mut self, mode: u32) -> &mut Self;
+
+        /// Passes custom flags (e.g. `O_NOFOLLOW`) to the underlying `open`
+        /// call for every volume, as
+        /// `std::os::unix::fs::OpenOptionsExt::custom_flags` does for
+        /// `fs::File`.
+        fn custom_flags(&mut self, flags: i32) -> &mut Self;
+    }
+
+    impl OpenOptionsExt for OpenOptions {
+        fn mode(&mut self, mode: u32) -> &mut OpenOptions {
+            self.mode = Some(mode);
+            self
+        }
+
+        fn custom_flags(&mut self, flags: i32) -> &mut OpenOptions {
+            self.custom_flags = Some(flags);
+            self
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,4 +1039,189 @@ mod tests {
 
         dir.close().expect("tempdir close error");
     }
+
+    #[test]
+    fn test_set_len() {
+        let dir = tempdir().expect("tempdir error");
+        let path = dir.path().join("test_set_len");
+        let data: [u8; 40] = [7; 40];
+
+        let mut file = SplitFile::create(path.as_path(), 15).expect("create error");
+        file.write(&data).expect("write error");
+        file.flush().expect("flush");
+
+        // shrink into the middle of the second volume
+        file.set_len(20).expect("set_len shrink error");
+        assert_eq!(file.len().expect("len error"), 20);
+        assert!(!NamingScheme::DottedIndex.by_index(&path, 3).is_file());
+
+        // grow past the current end, spanning a new volume
+        file.set_len(50).expect("set_len grow error");
+        assert_eq!(file.len().expect("len error"), 50);
+        assert!(NamingScheme::DottedIndex.by_index(&path, 4).is_file());
+
+        drop(file);
+
+        let mut rdata = [0u8; 20];
+        let mut file = SplitFile::open(path.as_path(), 15).expect("open error - read");
+        file.read(&mut rdata).expect("read error");
+        assert_eq!(rdata, data[..20]);
+
+        dir.close().expect("tempdir close error");
+    }
+
+    #[test]
+    fn test_set_len_aligned() {
+        let dir = tempdir().expect("tempdir error");
+        let path = dir.path().join("test_set_len_aligned");
+        let data: [u8; 40] = [7; 40];
+
+        let mut file = SplitFile::create(path.as_path(), 15).expect("create error");
+        file.write(&data).expect("write error");
+        file.flush().expect("flush");
+
+        // shrink to a size that is an exact multiple of volsize
+        file.set_len(30).expect("set_len shrink to aligned error");
+        assert_eq!(file.len().expect("len error"), 30);
+        assert!(!NamingScheme::DottedIndex.by_index(&path, 3).is_file());
+
+        // shrink again to a single, fully-sized volume
+        file.set_len(15).expect("set_len shrink to aligned error");
+        assert_eq!(file.len().expect("len error"), 15);
+        assert!(!NamingScheme::DottedIndex.by_index(&path, 2).is_file());
+
+        // shrink to zero
+        file.set_len(0).expect("set_len shrink to zero error");
+        assert_eq!(file.len().expect("len error"), 0);
+
+        drop(file);
+
+        let mut file = SplitFile::open(path.as_path(), 15).expect("open error - read");
+        assert_eq!(file.len().expect("len error"), 0);
+        let mut rdata = Vec::new();
+        assert_eq!(file.read(&mut rdata).expect("read error"), 0);
+
+        dir.close().expect("tempdir close error");
+    }
+
+    #[test]
+    fn test_metadata() {
+        let dir = tempdir().expect("tempdir error");
+        let path = dir.path().join("test_metadata");
+        let data: [u8; 40] = [3; 40];
+
+        let mut file = SplitFile::create(path.as_path(), 15).expect("create error");
+        file.write(&data).expect("write error");
+        file.flush().expect("flush");
+
+        let meta = file.metadata().expect("metadata error");
+        assert_eq!(meta.len(), 40);
+        assert_eq!(meta.volumes(), 3);
+        assert_eq!(meta.volume_sizes(), &[15, 15, 10]);
+        assert_eq!(meta.volume_paths()[0], path);
+        assert_eq!(meta.volume_paths()[2], NamingScheme::DottedIndex.by_index(&path, 3));
+
+        dir.close().expect("tempdir close error");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_options_ext() {
+        use crate::unix::OpenOptionsExt;
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().expect("tempdir error");
+        let path = dir.path().join("test_mode");
+
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path.as_path(), 15)
+            .expect("open error - mode");
+
+        let perms = fs::metadata(path.as_path()).expect("metadata error").permissions();
+        assert_eq!(perms.mode() & 0o777, 0o600);
+
+        dir.close().expect("tempdir close error");
+    }
+
+    #[test]
+    fn test_naming_scheme() {
+        let dir = tempdir().expect("tempdir error");
+        let path = dir.path().join("archive");
+        let data: [u8; 40] = [9; 40];
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .naming_scheme(NamingScheme::ZeroPadded { width: 3 })
+            .open(path.as_path(), 15)
+            .expect("open error - create");
+        file.write(&data).expect("write error");
+        file.flush().expect("flush");
+        drop(file);
+
+        assert!(dir.path().join("archive.002").is_file());
+        assert!(dir.path().join("archive.003").is_file());
+        assert!(!dir.path().join("archive.2").is_file());
+
+        let mut rdata = [0u8; 40];
+        let mut file = OpenOptions::new()
+            .read(true)
+            .naming_scheme(NamingScheme::ZeroPadded { width: 3 })
+            .open(path.as_path(), 15)
+            .expect("open error - read");
+        file.read(&mut rdata).expect("read error");
+        assert_eq!(rdata, data);
+
+        dir.close().expect("tempdir close error");
+    }
+
+    #[test]
+    fn test_buffered() {
+        let dir = tempdir().expect("tempdir error");
+        let path = dir.path().join("test_buffered");
+        let mut data: [u8; 50] = [0; 50];
+        for i in 0..data.len() {
+            data[i] = i as u8;
+        }
+
+        // write one byte at a time through a small buffer, straddling
+        // several volume boundaries (volsize 15, buffer_size 4)
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .buffer_size(4)
+            .open(path.as_path(), 15)
+            .expect("open error - create");
+        for b in data.iter() {
+            file.write(&[*b]).expect("write error");
+        }
+        file.flush().expect("flush error");
+        drop(file);
+
+        // read it back one byte at a time through a buffer too
+        let mut rdata: [u8; 50] = [0; 50];
+        let mut file = OpenOptions::new()
+            .read(true)
+            .buffer_size(4)
+            .open(path.as_path(), 15)
+            .expect("open error - read");
+        for b in rdata.iter_mut() {
+            let mut one = [0u8; 1];
+            file.read(&mut one).expect("read error");
+            *b = one[0];
+        }
+        assert_eq!(rdata, data);
+
+        // seeking should account for buffered-but-unread bytes
+        file.seek(SeekFrom::Start(10)).expect("seek error");
+        let mut rdata2: [u8; 10] = [0; 10];
+        file.read(&mut rdata2).expect("read error");
+        assert_eq!(rdata2, data[10..20]);
+        drop(file);
+
+        dir.close().expect("tempdir close error");
+    }
 }